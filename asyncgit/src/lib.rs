@@ -0,0 +1,4 @@
+pub mod error;
+pub mod patches;
+pub mod push;
+pub mod sync;
@@ -0,0 +1,134 @@
+use crate::{
+	error::{Error, Result},
+	sync::{patches::write_patches, utils::repo, CommitId},
+	AsyncGitNotification, CWD,
+};
+use crossbeam_channel::Sender;
+use std::{
+	path::PathBuf,
+	sync::{Arc, Mutex},
+	thread,
+};
+
+/// request to export a set of commits as `git format-patch` files
+#[derive(Default, Clone, Debug)]
+pub struct FormatPatchRequest {
+	///
+	pub commits: Vec<CommitId>,
+	///
+	pub output_dir: PathBuf,
+}
+
+#[derive(Default, Clone, Debug)]
+struct FormatPatchState {
+	request: FormatPatchRequest,
+}
+
+///
+pub struct AsyncFormatPatch {
+	state: Arc<Mutex<Option<FormatPatchState>>>,
+	last_result: Arc<Mutex<Option<String>>>,
+	sender: Sender<AsyncGitNotification>,
+}
+
+impl AsyncFormatPatch {
+	///
+	pub fn new(sender: &Sender<AsyncGitNotification>) -> Self {
+		Self {
+			state: Arc::new(Mutex::new(None)),
+			last_result: Arc::new(Mutex::new(None)),
+			sender: sender.clone(),
+		}
+	}
+
+	///
+	pub fn is_pending(&self) -> Result<bool> {
+		let state = self.state.lock()?;
+		Ok(state.is_some())
+	}
+
+	///
+	pub fn last_result(&self) -> Result<Option<String>> {
+		let res = self.last_result.lock()?;
+		Ok(res.clone())
+	}
+
+	///
+	pub fn request(&mut self, params: FormatPatchRequest) -> Result<()> {
+		log::trace!("request");
+
+		if self.is_pending()? {
+			return Ok(());
+		}
+
+		self.set_request(&params)?;
+
+		let arc_state = Arc::clone(&self.state);
+		let arc_res = Arc::clone(&self.last_result);
+		let sender = self.sender.clone();
+
+		thread::spawn(move || {
+			let res = repo(CWD).map_err(Error::from).and_then(
+				|repo| {
+					write_patches(
+						&repo,
+						&params.commits,
+						&params.output_dir,
+					)
+					.map(|_| ())
+				},
+			);
+
+			Self::set_result(&arc_res, res).expect("result error");
+
+			Self::clear_request(&arc_state).expect("clear error");
+
+			sender
+				.send(AsyncGitNotification::FormatPatch)
+				.expect("error sending format-patch");
+		});
+
+		Ok(())
+	}
+
+	fn set_request(&self, params: &FormatPatchRequest) -> Result<()> {
+		let mut state = self.state.lock()?;
+
+		if state.is_some() {
+			return Err(Error::Generic("pending request".into()));
+		}
+
+		*state = Some(FormatPatchState {
+			request: params.clone(),
+		});
+
+		Ok(())
+	}
+
+	fn clear_request(
+		state: &Arc<Mutex<Option<FormatPatchState>>>,
+	) -> Result<()> {
+		let mut state = state.lock()?;
+
+		*state = None;
+
+		Ok(())
+	}
+
+	fn set_result(
+		arc_result: &Arc<Mutex<Option<String>>>,
+		res: Result<()>,
+	) -> Result<()> {
+		let mut last_res = arc_result.lock()?;
+
+		*last_res = match res {
+			Ok(_) => None,
+			Err(e) => {
+				log::error!("format-patch error: {}", e);
+				Some(e.to_string())
+			}
+		};
+
+		Ok(())
+	}
+}
@@ -0,0 +1,116 @@
+use std::{
+	collections::HashMap,
+	hash::Hash,
+	sync::Mutex,
+	time::{Duration, Instant},
+};
+
+struct Entry<V> {
+	value: V,
+	inserted_at: Instant,
+}
+
+/// small bounded, time-to-live cache, in the spirit of rgit's moka-backed
+/// commit/readme caches, so repeatedly revisiting the same revision or file
+/// is served from memory instead of hitting git2 again
+pub struct TtlCache<K, V> {
+	entries: Mutex<HashMap<K, Entry<V>>>,
+	capacity: usize,
+	ttl: Duration,
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> TtlCache<K, V> {
+	///
+	pub fn new(capacity: usize, ttl: Duration) -> Self {
+		Self {
+			entries: Mutex::new(HashMap::new()),
+			capacity,
+			ttl,
+		}
+	}
+
+	/// returns the cached value for `key`, if present and not yet expired
+	pub fn get(&self, key: &K) -> Option<V> {
+		let mut entries = self.entries.lock().ok()?;
+
+		if let Some(entry) = entries.get(key) {
+			if entry.inserted_at.elapsed() < self.ttl {
+				return Some(entry.value.clone());
+			}
+
+			entries.remove(key);
+		}
+
+		None
+	}
+
+	/// inserts `value` for `key`, evicting the oldest entry first if the
+	/// cache is at capacity
+	pub fn insert(&self, key: K, value: V) {
+		let mut entries = match self.entries.lock() {
+			Ok(entries) => entries,
+			Err(_) => return,
+		};
+
+		if entries.len() >= self.capacity
+			&& !entries.contains_key(&key)
+		{
+			if let Some(oldest) = entries
+				.iter()
+				.min_by_key(|(_, entry)| entry.inserted_at)
+				.map(|(key, _)| key.clone())
+			{
+				entries.remove(&oldest);
+			}
+		}
+
+		entries.insert(
+			key,
+			Entry {
+				value,
+				inserted_at: Instant::now(),
+			},
+		);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_hit_and_miss() {
+		let cache: TtlCache<u32, &str> =
+			TtlCache::new(2, Duration::from_secs(60));
+
+		assert_eq!(cache.get(&1), None);
+
+		cache.insert(1, "one");
+
+		assert_eq!(cache.get(&1), Some("one"));
+	}
+
+	#[test]
+	fn test_ttl_expiry() {
+		let cache: TtlCache<u32, &str> =
+			TtlCache::new(2, Duration::from_millis(1));
+
+		cache.insert(1, "one");
+
+		std::thread::sleep(Duration::from_millis(10));
+
+		assert_eq!(cache.get(&1), None);
+	}
+
+	#[test]
+	fn test_capacity_eviction() {
+		let cache: TtlCache<u32, &str> =
+			TtlCache::new(1, Duration::from_secs(60));
+
+		cache.insert(1, "one");
+		cache.insert(2, "two");
+
+		assert_eq!(cache.get(&1), None);
+		assert_eq!(cache.get(&2), Some("two"));
+	}
+}
@@ -0,0 +1,5 @@
+pub mod blob;
+pub mod cache;
+pub mod patches;
+pub mod rebase;
+pub mod status;
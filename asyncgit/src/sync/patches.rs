@@ -0,0 +1,126 @@
+use super::CommitId;
+use crate::error::Result;
+use std::path::{Path, PathBuf};
+
+/// render a single commit as a `git format-patch` style mbox block,
+/// following the approach rgit uses for its `git2::Email` based patch view
+fn format_patch(
+	repo: &git2::Repository,
+	commit_id: CommitId,
+	index: usize,
+	total: usize,
+) -> Result<String> {
+	let commit = repo.find_commit(commit_id.into())?;
+
+	let mut opts = git2::EmailCreateOptions::new();
+	opts.patch_no(index + 1)
+		.total_patches(total)
+		.exclude_subject_patch_marker(total <= 1);
+
+	let email = git2::Email::from_commit(&commit, &mut opts)?;
+
+	Ok(String::from_utf8_lossy(&email).into_owned())
+}
+
+/// render a range of commits (oldest first) as `git format-patch`-style
+/// mbox text, numbering multi-commit series as `[PATCH n/m]`
+pub fn format_patches(
+	repo: &git2::Repository,
+	commits: &[CommitId],
+) -> Result<Vec<String>> {
+	let total = commits.len();
+
+	commits
+		.iter()
+		.enumerate()
+		.map(|(index, id)| format_patch(repo, *id, index, total))
+		.collect()
+}
+
+/// render `commits` and write one `NNNN-slug.patch` file per commit into
+/// `output_dir`, returning the paths written
+pub fn write_patches(
+	repo: &git2::Repository,
+	commits: &[CommitId],
+	output_dir: &Path,
+) -> Result<Vec<PathBuf>> {
+	let total = commits.len();
+	let mut paths = Vec::with_capacity(total);
+
+	for (index, id) in commits.iter().enumerate() {
+		let commit = repo.find_commit((*id).into())?;
+		let patch = format_patch(repo, *id, index, total)?;
+
+		let filename = format!(
+			"{:04}-{}.patch",
+			index + 1,
+			slugify(commit.summary().unwrap_or_default())
+		);
+		let path = output_dir.join(filename);
+
+		std::fs::write(&path, patch)?;
+
+		paths.push(path);
+	}
+
+	Ok(paths)
+}
+
+/// turn a commit subject into a filename-safe slug, e.g. for `NNNN-slug.patch`
+fn slugify(subject: &str) -> String {
+	subject
+		.chars()
+		.map(|c| {
+			if c.is_ascii_alphanumeric() {
+				c.to_ascii_lowercase()
+			} else {
+				'-'
+			}
+		})
+		.collect::<String>()
+		.split('-')
+		.filter(|part| !part.is_empty())
+		.collect::<Vec<_>>()
+		.join("-")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::tests::{repo_init, write_commit_file};
+
+	#[test]
+	fn test_format_patches_smoke() {
+		let (_td, repo) = repo_init().unwrap();
+
+		let c1 =
+			write_commit_file(&repo, "a.txt", "a", "first commit");
+		let c2 =
+			write_commit_file(&repo, "b.txt", "b", "second commit");
+
+		let patches =
+			format_patches(&repo, &[c1, c2]).unwrap();
+
+		assert_eq!(patches.len(), 2);
+		assert!(patches[0].contains("Subject: [PATCH 1/2]"));
+		assert!(patches[0].contains("first commit"));
+		assert!(patches[1].contains("Subject: [PATCH 2/2]"));
+		assert!(patches[1].contains("second commit"));
+	}
+
+	#[test]
+	fn test_write_patches_smoke() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+
+		let c1 =
+			write_commit_file(&repo, "a.txt", "a", "first commit");
+
+		let paths =
+			write_patches(&repo, &[c1], root).unwrap();
+
+		assert_eq!(paths.len(), 1);
+		assert!(paths[0].ends_with("0001-first-commit.patch"));
+		assert!(paths[0].exists());
+	}
+}
@@ -103,13 +103,436 @@ pub fn get_rebase_progress(
 ///
 #[allow(dead_code)]
 pub fn abort_rebase(repo: &git2::Repository) -> Result<()> {
-	let mut rebase = repo.open_rebase(None)?;
+	let mut rebase = open_rebase(repo)?;
 
 	rebase.abort()?;
 
 	Ok(())
 }
 
+fn open_rebase(repo: &git2::Repository) -> Result<git2::Rebase<'_>> {
+	Ok(repo.open_rebase(None)?)
+}
+
+/// continue an in-progress, conflicted rebase (`RepoState::Rebase`) assuming
+/// the conflicts have already been resolved and staged, then drive the
+/// remaining operations with the same conflict-detection loop as
+/// [`conflict_free_rebase`]
+#[allow(dead_code)]
+pub fn continue_rebase(repo: &git2::Repository) -> Result<RebaseState> {
+	let mut rebase = open_rebase(repo)?;
+	let signature =
+		crate::sync::commit::signature_allow_undefined_name(repo)?;
+
+	rebase.commit(None, &signature, None)?;
+
+	while let Some(op) = rebase.next() {
+		let _op = op?;
+
+		if repo.index()?.has_conflicts() {
+			return Ok(RebaseState::Conflicted);
+		}
+
+		rebase.commit(None, &signature, None)?;
+	}
+
+	if repo.index()?.has_conflicts() {
+		return Ok(RebaseState::Conflicted);
+	}
+
+	rebase.finish(Some(&signature))?;
+
+	Ok(RebaseState::Finished)
+}
+
+/// skip the currently conflicted operation of an in-progress rebase without
+/// committing it, then continue with the remaining operations
+#[allow(dead_code)]
+pub fn skip_rebase_operation(
+	repo: &git2::Repository,
+) -> Result<RebaseState> {
+	let mut rebase = open_rebase(repo)?;
+	let signature =
+		crate::sync::commit::signature_allow_undefined_name(repo)?;
+
+	// the currently active operation already applied its patch to the
+	// index/workdir; undo that before moving on so the unresolved conflict
+	// leaves no trace, same as `Drop` in `execute_rebase_todo`
+	let head = repo.head()?.peel_to_commit()?;
+	repo.reset(head.as_object(), git2::ResetType::Hard, None)?;
+
+	// do not commit the currently active (conflicted) operation, just move
+	// on to the next one
+	while let Some(op) = rebase.next() {
+		let _op = op?;
+
+		if repo.index()?.has_conflicts() {
+			return Ok(RebaseState::Conflicted);
+		}
+
+		rebase.commit(None, &signature, None)?;
+	}
+
+	if repo.index()?.has_conflicts() {
+		return Ok(RebaseState::Conflicted);
+	}
+
+	rebase.finish(Some(&signature))?;
+
+	Ok(RebaseState::Finished)
+}
+
+/// action to take for a single entry of an interactive rebase todo list
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum RebaseTodoAction {
+	///
+	Pick,
+	///
+	Reword,
+	///
+	Edit,
+	///
+	Squash,
+	///
+	Fixup,
+	///
+	Drop,
+}
+
+/// a single entry of an interactive rebase todo list
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct RebaseTodoEntry {
+	///
+	pub action: RebaseTodoAction,
+	///
+	pub commit: CommitId,
+	///
+	pub msg: String,
+}
+
+/// ordered list of entries driving an interactive rebase, oldest commit first
+pub type RebaseTodo = Vec<RebaseTodoEntry>;
+
+/// state returned while driving an interactive rebase forward
+#[derive(PartialEq, Debug)]
+pub enum RebaseTodoState {
+	///
+	Finished,
+	///
+	ConflictedAt(CommitId),
+	///
+	StoppedForEdit(CommitId),
+}
+
+/// build the default (all `Pick`) todo list for the commits that would be
+/// replayed when rebasing onto `commit`
+#[allow(dead_code)]
+pub fn rebase_todo(
+	repo: &git2::Repository,
+	commit: &git2::AnnotatedCommit,
+) -> Result<RebaseTodo> {
+	let mut rebase = repo.rebase(None, Some(commit), None, None)?;
+
+	let mut todo = RebaseTodo::new();
+
+	while let Some(op) = rebase.next() {
+		let op = op?;
+		let id = op.id();
+		let msg = repo
+			.find_commit(id)?
+			.message()
+			.unwrap_or_default()
+			.to_string();
+
+		todo.push(RebaseTodoEntry {
+			action: RebaseTodoAction::Pick,
+			commit: CommitId::from(id),
+			msg,
+		});
+	}
+
+	// we only replayed the range to learn the commits and their messages
+	rebase.abort()?;
+
+	Ok(todo)
+}
+
+/// move the entry at `from` to `to`, shifting the entries in between
+#[allow(dead_code)]
+pub fn move_todo_entry(todo: &mut RebaseTodo, from: usize, to: usize) {
+	if from < todo.len() && to < todo.len() {
+		let entry = todo.remove(from);
+		todo.insert(to, entry);
+	}
+}
+
+/// change the action of the entry at `idx`
+#[allow(dead_code)]
+pub fn set_todo_action(
+	todo: &mut RebaseTodo,
+	idx: usize,
+	action: RebaseTodoAction,
+) {
+	if let Some(entry) = todo.get_mut(idx) {
+		entry.action = action;
+	}
+}
+
+/// change the message of the entry at `idx`, used for `Reword`/`Squash`
+#[allow(dead_code)]
+pub fn set_todo_message(todo: &mut RebaseTodo, idx: usize, msg: String) {
+	if let Some(entry) = todo.get_mut(idx) {
+		entry.msg = msg;
+	}
+}
+
+/// a leading `Squash`/`Fixup` has nothing to fold into: the commit before
+/// the first entry is the upstream tip we are rebasing onto, which lives
+/// outside the range being rebased, so folding into it would silently
+/// rewrite history the caller never asked to touch (this mirrors real
+/// git's "cannot 'squash' without a previous commit")
+fn validate_rebase_todo(todo: &RebaseTodo) -> Result<()> {
+	if let Some(entry) = todo.first() {
+		if matches!(
+			entry.action,
+			RebaseTodoAction::Squash | RebaseTodoAction::Fixup
+		) {
+			return Err(Error::Generic(String::from(
+				"cannot squash/fixup without a previous commit",
+			)));
+		}
+	}
+
+	Ok(())
+}
+
+/// drive each `todo` entry's action to completion, stopping at the first
+/// conflict or `Edit` so the caller can prompt the user
+///
+/// this replays every entry with `Repository::cherrypick_commit` instead of
+/// `git2::Rebase::next`/`commit`/`finish`: folding a `Squash`/`Fixup` into
+/// its predecessor has to build a *new* commit whose parent skips the
+/// just-replayed one, and `git2::Rebase` has no API to tell it a fold
+/// happened, so its internal "last applied commit" bookkeeping (used as the
+/// parent base for the following `next()`/`commit()`, and to move the
+/// branch ref on `finish()`) would stay pointed at the unfolded commit,
+/// silently discarding the squash/fixup on every following entry
+#[allow(dead_code)]
+pub fn execute_rebase_todo(
+	repo: &git2::Repository,
+	commit: &git2::AnnotatedCommit,
+	todo: &RebaseTodo,
+) -> Result<RebaseTodoState> {
+	validate_rebase_todo(todo)?;
+
+	let signature =
+		crate::sync::commit::signature_allow_undefined_name(repo)?;
+
+	let head_ref_name = repo.head()?.name().map(String::from);
+
+	// the commit each entry is replayed on top of; starts at the upstream
+	// tip we are rebasing onto
+	let parent = repo.find_commit(commit.id())?;
+
+	drive_rebase_todo(
+		repo,
+		&signature,
+		head_ref_name,
+		parent,
+		todo,
+		Vec::new(),
+	)
+}
+
+/// resume an interactive rebase that [`execute_rebase_todo`] (or a previous
+/// call to this function) stopped with `ConflictedAt`/`StoppedForEdit`
+///
+/// `todo` must be the entries that had not yet been executed at the stop,
+/// *including* the one that stopped it. For a conflict, the caller is
+/// expected to have resolved and staged the conflict in the repo's real
+/// index first, the same convention as [`continue_rebase`]. For an edit
+/// stop, that entry's diff is already staged (checked out by the previous
+/// call, but never committed), and the caller may have changed the index
+/// further before resuming. Either way `HEAD` must still be the detached
+/// commit the previous call left it pointed at.
+#[allow(dead_code)]
+pub fn continue_rebase_todo(
+	repo: &git2::Repository,
+	todo: &RebaseTodo,
+) -> Result<RebaseTodoState> {
+	if repo.index()?.has_conflicts() {
+		return Err(Error::RebaseConflict);
+	}
+
+	let (entry, rest) = todo.split_first().ok_or_else(|| {
+		Error::Generic(String::from(
+			"no rebase todo entry left to resume",
+		))
+	})?;
+
+	let signature =
+		crate::sync::commit::signature_allow_undefined_name(repo)?;
+	let head_ref_name = repo.head()?.name().map(String::from);
+	let mut parent = repo.head()?.peel_to_commit()?;
+	let mut pending_squash_msgs: Vec<String> = Vec::new();
+
+	if entry.action != RebaseTodoAction::Drop {
+		let original = repo.find_commit(entry.commit.into())?;
+		let mut index = repo.index()?;
+		let tree = repo.find_tree(index.write_tree()?)?;
+
+		let (author, message, commit_parent) = todo_entry_commit_spec(
+			&parent,
+			&original,
+			entry,
+			&mut pending_squash_msgs,
+		)?;
+
+		let new_id = repo.commit(
+			None,
+			&author,
+			&signature,
+			message.as_str(),
+			&tree,
+			&[&commit_parent],
+		)?;
+
+		parent = repo.find_commit(new_id)?;
+	}
+
+	drive_rebase_todo(
+		repo,
+		&signature,
+		head_ref_name,
+		parent,
+		rest,
+		pending_squash_msgs,
+	)
+}
+
+/// author, message and parent commit `entry` should be committed with,
+/// folding `Squash`/`Fixup` into `parent` itself: same tree as `parent`'s
+/// snapshot plus this entry's diff, but parented on `parent`'s own parent
+/// so `parent` (the unfolded commit) is skipped over rather than kept in
+/// history, keeping `parent`'s authorship since that's the commit being
+/// kept and expanded, not the one being folded away
+fn todo_entry_commit_spec<'repo>(
+	parent: &git2::Commit<'repo>,
+	original: &git2::Commit<'repo>,
+	entry: &RebaseTodoEntry,
+	pending_squash_msgs: &mut Vec<String>,
+) -> Result<(git2::Signature<'repo>, String, git2::Commit<'repo>)> {
+	Ok(match entry.action {
+		RebaseTodoAction::Reword => {
+			(original.author(), entry.msg.clone(), parent.clone())
+		}
+		RebaseTodoAction::Squash | RebaseTodoAction::Fixup => {
+			if entry.action == RebaseTodoAction::Squash {
+				pending_squash_msgs.push(entry.msg.clone());
+			}
+
+			let msg = if pending_squash_msgs.is_empty() {
+				parent.message().unwrap_or_default().to_string()
+			} else {
+				let mut combined = vec![parent
+					.message()
+					.unwrap_or_default()
+					.to_string()];
+				combined.append(pending_squash_msgs);
+				combined.join("\n\n")
+			};
+
+			(parent.author(), msg, parent.parent(0)?)
+		}
+		// an `Edit` only reaches here when resuming after its stop, and is
+		// then committed like a `Pick`
+		RebaseTodoAction::Pick | RebaseTodoAction::Edit => (
+			original.author(),
+			original.message().unwrap_or_default().to_string(),
+			parent.clone(),
+		),
+		RebaseTodoAction::Drop => {
+			unreachable!("handled by the caller")
+		}
+	})
+}
+
+/// replay `todo` starting from `parent`, committing each entry as
+/// [`todo_entry_commit_spec`] describes and stopping early at the first
+/// conflict or `Edit`; shared between [`execute_rebase_todo`] (starting
+/// fresh from the onto commit) and [`continue_rebase_todo`] (resuming after
+/// a stop, with `pending_squash_msgs` carried over)
+fn drive_rebase_todo<'repo>(
+	repo: &'repo git2::Repository,
+	signature: &git2::Signature,
+	head_ref_name: Option<String>,
+	mut parent: git2::Commit<'repo>,
+	todo: &[RebaseTodoEntry],
+	mut pending_squash_msgs: Vec<String>,
+) -> Result<RebaseTodoState> {
+	for entry in todo {
+		let original = repo.find_commit(entry.commit.into())?;
+
+		let mut index =
+			repo.cherrypick_commit(&original, &parent, 0, None)?;
+
+		if index.has_conflicts() {
+			repo.checkout_index(Some(&mut index), None)?;
+			repo.set_index(&mut index)?;
+			repo.set_head_detached(parent.id())?;
+			return Ok(RebaseTodoState::ConflictedAt(entry.commit));
+		}
+
+		if entry.action == RebaseTodoAction::Drop {
+			continue;
+		}
+
+		if entry.action == RebaseTodoAction::Edit {
+			repo.checkout_index(Some(&mut index), None)?;
+			repo.set_index(&mut index)?;
+			repo.set_head_detached(parent.id())?;
+			return Ok(RebaseTodoState::StoppedForEdit(entry.commit));
+		}
+
+		let tree = repo.find_tree(index.write_tree_to(repo)?)?;
+
+		let (author, message, commit_parent) = todo_entry_commit_spec(
+			&parent,
+			&original,
+			entry,
+			&mut pending_squash_msgs,
+		)?;
+
+		let new_id = repo.commit(
+			None,
+			&author,
+			signature,
+			message.as_str(),
+			&tree,
+			&[&commit_parent],
+		)?;
+
+		parent = repo.find_commit(new_id)?;
+	}
+
+	repo.checkout_tree(parent.as_object(), None)?;
+
+	match head_ref_name.as_deref() {
+		Some(name) if name != "HEAD" => {
+			repo.find_reference(name)?.set_target(
+				parent.id(),
+				"rebase (finish): returning to refs",
+			)?;
+			repo.set_head(name)?;
+		}
+		_ => {
+			repo.set_head_detached(parent.id())?;
+		}
+	}
+
+	Ok(RebaseTodoState::Finished)
+}
+
 #[cfg(test)]
 mod test_conflict_free_rebase {
 	use crate::sync::{
@@ -182,13 +605,14 @@ mod test_rebase {
 	use crate::sync::{
 		checkout_branch, create_branch,
 		rebase::{
-			abort_rebase, get_rebase_progress, RebaseProgress,
-			RebaseState,
+			abort_rebase, continue_rebase, get_rebase_progress,
+			skip_rebase_operation, RebaseProgress, RebaseState,
 		},
 		rebase_branch, repo_state,
 		tests::{repo_init, write_commit_file},
 		RepoState,
 	};
+	use std::{fs::File, io::Write, path::Path};
 
 	#[test]
 	fn test_conflicted_abort() {
@@ -230,4 +654,352 @@ mod test_rebase {
 
 		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
 	}
+
+	#[test]
+	fn test_conflicted_continue() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path = root.as_os_str().to_str().unwrap();
+
+		write_commit_file(&repo, "test.txt", "test1", "commit1");
+
+		create_branch(repo_path, "foo").unwrap();
+
+		write_commit_file(&repo, "test.txt", "test2", "commit2");
+
+		checkout_branch(repo_path, "refs/heads/master").unwrap();
+
+		write_commit_file(&repo, "test.txt", "test3", "commit3");
+
+		checkout_branch(repo_path, "refs/heads/foo").unwrap();
+
+		let r = rebase_branch(repo_path, "master").unwrap();
+
+		assert_eq!(r, RebaseState::Conflicted);
+
+		// resolve the conflict and stage it, like a user would
+		File::create(root.join("test.txt"))
+			.unwrap()
+			.write_all(b"resolved")
+			.unwrap();
+
+		let mut index = repo.index().unwrap();
+		index.add_path(Path::new("test.txt")).unwrap();
+		index.write().unwrap();
+
+		let r = continue_rebase(&repo).unwrap();
+
+		assert_eq!(r, RebaseState::Finished);
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+	}
+
+	#[test]
+	fn test_conflicted_skip() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path = root.as_os_str().to_str().unwrap();
+
+		write_commit_file(&repo, "test.txt", "test1", "commit1");
+
+		create_branch(repo_path, "foo").unwrap();
+
+		// foo gets two commits: the first conflicts with master below, the
+		// second is independent and should still apply after the skip
+		write_commit_file(&repo, "test.txt", "test2", "commit2");
+		write_commit_file(&repo, "other.txt", "other", "commit3");
+
+		checkout_branch(repo_path, "refs/heads/master").unwrap();
+
+		write_commit_file(&repo, "test.txt", "test3", "commit4");
+
+		checkout_branch(repo_path, "refs/heads/foo").unwrap();
+
+		let r = rebase_branch(repo_path, "master").unwrap();
+
+		assert_eq!(r, RebaseState::Conflicted);
+		assert_eq!(
+			get_rebase_progress(&repo).unwrap(),
+			RebaseProgress {
+				current: 0,
+				steps: 2
+			}
+		);
+
+		let r = skip_rebase_operation(&repo).unwrap();
+
+		assert_eq!(r, RebaseState::Finished);
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+
+		// the skipped commit's change never lands, but the later,
+		// independent commit on top of it still does
+		assert!(root.join("other.txt").exists());
+		assert_eq!(
+			std::fs::read_to_string(root.join("test.txt")).unwrap(),
+			"test3"
+		);
+	}
+}
+
+#[cfg(test)]
+mod test_rebase_todo {
+	use crate::sync::{
+		checkout_branch, create_branch,
+		rebase::{
+			continue_rebase_todo, execute_rebase_todo, rebase_todo,
+			set_todo_action, set_todo_message, RebaseTodoAction,
+			RebaseTodoEntry, RebaseTodoState,
+		},
+		tests::{repo_init, write_commit_file},
+	};
+	use git2::{BranchType, Repository};
+
+	fn log_messages(repo: &Repository) -> Vec<String> {
+		let mut revwalk = repo.revwalk().unwrap();
+		revwalk.push_head().unwrap();
+
+		revwalk
+			.map(|id| {
+				repo.find_commit(id.unwrap())
+					.unwrap()
+					.message()
+					.unwrap_or_default()
+					.trim()
+					.to_string()
+			})
+			.collect()
+	}
+
+	fn annotated_head(repo: &Repository) -> git2::AnnotatedCommit<'_> {
+		let head = repo.head().unwrap();
+		repo.reference_to_annotated_commit(&head).unwrap()
+	}
+
+	#[test]
+	fn test_pick_and_reword() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+
+		write_commit_file(&repo, "a.txt", "a", "commit1");
+		let onto = annotated_head(&repo);
+
+		write_commit_file(&repo, "b.txt", "b", "commit2");
+		write_commit_file(&repo, "c.txt", "c", "commit3");
+
+		let mut todo = rebase_todo(&repo, &onto).unwrap();
+		assert_eq!(todo.len(), 2);
+
+		set_todo_action(&mut todo, 1, RebaseTodoAction::Reword);
+		set_todo_message(&mut todo, 1, String::from("reworded"));
+
+		let state = execute_rebase_todo(&repo, &onto, &todo).unwrap();
+
+		assert_eq!(state, RebaseTodoState::Finished);
+		assert_eq!(
+			log_messages(&repo),
+			vec![
+				String::from("reworded"),
+				String::from("commit2"),
+				String::from("commit1"),
+			]
+		);
+		assert!(root.join("b.txt").exists());
+		assert!(root.join("c.txt").exists());
+	}
+
+	#[test]
+	fn test_drop() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+
+		write_commit_file(&repo, "a.txt", "a", "commit1");
+		let onto = annotated_head(&repo);
+
+		write_commit_file(&repo, "b.txt", "b", "commit2");
+
+		let mut todo = rebase_todo(&repo, &onto).unwrap();
+		set_todo_action(&mut todo, 0, RebaseTodoAction::Drop);
+
+		let state = execute_rebase_todo(&repo, &onto, &todo).unwrap();
+
+		assert_eq!(state, RebaseTodoState::Finished);
+		assert!(!root.join("b.txt").exists());
+	}
+
+	#[test]
+	fn test_squash_folds_into_one_commit() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+
+		write_commit_file(&repo, "a.txt", "a", "commit1");
+		let onto = annotated_head(&repo);
+
+		write_commit_file(&repo, "b.txt", "b", "commit2");
+		write_commit_file(&repo, "c.txt", "c", "commit3");
+
+		let mut todo = rebase_todo(&repo, &onto).unwrap();
+		set_todo_action(&mut todo, 1, RebaseTodoAction::Squash);
+
+		let state = execute_rebase_todo(&repo, &onto, &todo).unwrap();
+
+		assert_eq!(state, RebaseTodoState::Finished);
+
+		// the two picked commits collapsed into a single commit on top of
+		// `onto`, with both messages combined
+		let messages = log_messages(&repo);
+		assert_eq!(messages.len(), 2);
+		assert!(messages[0].contains("commit2"));
+		assert!(messages[0].contains("commit3"));
+		assert!(root.join("b.txt").exists());
+		assert!(root.join("c.txt").exists());
+	}
+
+	#[test]
+	fn test_fixup_discards_message() {
+		let (_td, repo) = repo_init().unwrap();
+
+		write_commit_file(&repo, "a.txt", "a", "commit1");
+		let onto = annotated_head(&repo);
+
+		write_commit_file(&repo, "b.txt", "b", "commit2");
+		write_commit_file(&repo, "c.txt", "c", "commit3");
+
+		let mut todo = rebase_todo(&repo, &onto).unwrap();
+		set_todo_action(&mut todo, 1, RebaseTodoAction::Fixup);
+
+		let state = execute_rebase_todo(&repo, &onto, &todo).unwrap();
+
+		assert_eq!(state, RebaseTodoState::Finished);
+
+		let messages = log_messages(&repo);
+		assert_eq!(messages.len(), 2);
+		assert_eq!(messages[0], "commit2");
+	}
+
+	#[test]
+	fn test_edit_stops() {
+		let (_td, repo) = repo_init().unwrap();
+
+		write_commit_file(&repo, "a.txt", "a", "commit1");
+		let onto = annotated_head(&repo);
+
+		let c2 = write_commit_file(&repo, "b.txt", "b", "commit2");
+
+		let mut todo = rebase_todo(&repo, &onto).unwrap();
+		set_todo_action(&mut todo, 0, RebaseTodoAction::Edit);
+
+		let state = execute_rebase_todo(&repo, &onto, &todo).unwrap();
+
+		assert_eq!(state, RebaseTodoState::StoppedForEdit(c2));
+	}
+
+	#[test]
+	fn test_leading_squash_is_rejected() {
+		let (_td, repo) = repo_init().unwrap();
+
+		write_commit_file(&repo, "a.txt", "a", "commit1");
+		let onto = annotated_head(&repo);
+
+		write_commit_file(&repo, "b.txt", "b", "commit2");
+
+		let mut todo = rebase_todo(&repo, &onto).unwrap();
+		set_todo_action(&mut todo, 0, RebaseTodoAction::Squash);
+
+		assert!(execute_rebase_todo(&repo, &onto, &todo).is_err());
+
+		let mut todo = rebase_todo(&repo, &onto).unwrap();
+		set_todo_action(&mut todo, 0, RebaseTodoAction::Fixup);
+
+		assert!(execute_rebase_todo(&repo, &onto, &todo).is_err());
+	}
+
+	#[test]
+	fn test_conflict_then_continue() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path = root.as_os_str().to_str().unwrap();
+
+		write_commit_file(&repo, "test.txt", "base", "commit1");
+
+		create_branch(repo_path, "foo").unwrap();
+		let c2 = write_commit_file(
+			&repo,
+			"test.txt",
+			"foo-edit",
+			"commit2",
+		);
+
+		checkout_branch(repo_path, "refs/heads/master").unwrap();
+		write_commit_file(&repo, "test.txt", "master-edit", "commit3");
+
+		checkout_branch(repo_path, "refs/heads/foo").unwrap();
+
+		let master =
+			repo.find_branch("master", BranchType::Local).unwrap();
+		let onto = repo
+			.reference_to_annotated_commit(&master.into_reference())
+			.unwrap();
+
+		let todo = vec![RebaseTodoEntry {
+			action: RebaseTodoAction::Pick,
+			commit: c2,
+			msg: String::from("commit2"),
+		}];
+
+		let state = execute_rebase_todo(&repo, &onto, &todo).unwrap();
+		assert_eq!(state, RebaseTodoState::ConflictedAt(c2));
+
+		// resolve the conflict and stage it, like a user would
+		std::fs::write(root.join("test.txt"), "resolved").unwrap();
+		let mut index = repo.index().unwrap();
+		index.add_path(std::path::Path::new("test.txt")).unwrap();
+		index.write().unwrap();
+
+		let state = continue_rebase_todo(&repo, &todo).unwrap();
+		assert_eq!(state, RebaseTodoState::Finished);
+
+		assert_eq!(
+			std::fs::read_to_string(root.join("test.txt")).unwrap(),
+			"resolved"
+		);
+	}
+
+	#[test]
+	fn test_edit_then_continue() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+
+		write_commit_file(&repo, "a.txt", "a", "commit1");
+		let onto = annotated_head(&repo);
+
+		write_commit_file(&repo, "b.txt", "b", "commit2");
+		write_commit_file(&repo, "c.txt", "c", "commit3");
+
+		let mut todo = rebase_todo(&repo, &onto).unwrap();
+		set_todo_action(&mut todo, 0, RebaseTodoAction::Edit);
+
+		let state = execute_rebase_todo(&repo, &onto, &todo).unwrap();
+		assert!(matches!(state, RebaseTodoState::StoppedForEdit(_)));
+
+		// amend the stopped commit with an extra file before resuming,
+		// like a user editing during the stop would
+		std::fs::write(root.join("extra.txt"), "extra").unwrap();
+		let mut index = repo.index().unwrap();
+		index.add_path(std::path::Path::new("extra.txt")).unwrap();
+		index.write().unwrap();
+
+		let state = continue_rebase_todo(&repo, &todo).unwrap();
+		assert_eq!(state, RebaseTodoState::Finished);
+
+		assert_eq!(
+			log_messages(&repo),
+			vec![
+				String::from("commit3"),
+				String::from("commit2"),
+				String::from("commit1"),
+			]
+		);
+		assert!(root.join("b.txt").exists());
+		assert!(root.join("c.txt").exists());
+		assert!(root.join("extra.txt").exists());
+	}
 }
@@ -0,0 +1,367 @@
+use crate::error::Result;
+use crossbeam_channel::{unbounded, Receiver, Sender};
+use git2::Repository;
+use std::{
+	collections::HashSet,
+	path::Path,
+	sync::{
+		atomic::{AtomicUsize, Ordering},
+		Arc, Mutex,
+	},
+	thread,
+	time::Duration,
+};
+
+/// number of worker threads used by the parallel status scan
+const WORKERS: usize = 4;
+
+/// the status of a single path relative to the working tree/index/HEAD
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum StatusItemType {
+	///
+	New,
+	///
+	Modified,
+	///
+	Deleted,
+	///
+	Renamed,
+	///
+	Typechange,
+	///
+	Conflicted,
+}
+
+impl From<git2::Status> for StatusItemType {
+	fn from(s: git2::Status) -> Self {
+		if s.is_conflicted() {
+			Self::Conflicted
+		} else if s.is_wt_deleted() || s.is_index_deleted() {
+			Self::Deleted
+		} else if s.is_wt_renamed() || s.is_index_renamed() {
+			Self::Renamed
+		} else if s.is_wt_typechange() || s.is_index_typechange() {
+			Self::Typechange
+		} else if s.is_wt_new() || s.is_index_new() {
+			Self::New
+		} else {
+			Self::Modified
+		}
+	}
+}
+
+/// a single working-tree/index entry and its status
+#[derive(Clone, Debug)]
+pub struct StatusItem {
+	///
+	pub path: String,
+	///
+	pub status: StatusItemType,
+}
+
+/// which side of `git status` to compute
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum StatusType {
+	///
+	WorkingDir,
+	///
+	Stage,
+}
+
+/// one or more pathspec patterns scoping a single `repo.statuses()` call to
+/// a slice of the working tree; one worker's unit of work
+type Scope = Vec<String>;
+
+/// work queue of scopes still to be scanned; multiple workers popping off
+/// the same `crossbeam_channel` receiver behave like a work-stealing pool,
+/// and `pending` tracks in-flight scopes so workers know when the scan is
+/// complete
+struct WorkQueue {
+	sender: Sender<Scope>,
+	receiver: Receiver<Scope>,
+	pending: AtomicUsize,
+}
+
+/// compute the status of `repo_path`'s working tree (or stage)
+///
+/// rather than one `repo.statuses()` call walking the whole tree
+/// single-threaded, the scan is split into one scope per top-level
+/// directory (plus one bundling top-level loose files), and a small worker
+/// pool runs a separate, pathspec-scoped `repo.statuses()` call per scope
+/// concurrently, following jj's parallel filesystem-traversal technique to
+/// overlap each call's stat/ignore-check syscalls across cores. Scoping by
+/// pathspec (rather than walking the filesystem and looking status up
+/// afterwards) means deleted paths are still reported even though they no
+/// longer exist on disk, and submodules fall out as the single leaf status
+/// entry `repo.statuses()` already gives them, so neither needs special
+/// handling here.
+pub fn status(
+	repo_path: &str,
+	status_type: StatusType,
+) -> Result<Vec<StatusItem>> {
+	let repo = crate::sync::utils::repo(repo_path)?;
+	let workdir = repo.workdir().ok_or(crate::error::Error::NoWorkDir)?;
+
+	let scopes = top_level_scopes(&repo, workdir)?;
+
+	let mut items = parallel_status(repo_path, status_type, scopes);
+
+	// scheduling across workers is non-deterministic; sort so the ordering
+	// stays stable between refreshes regardless of which worker finished a
+	// scope first
+	items.sort_by(|a, b| a.path.cmp(&b.path));
+
+	Ok(items)
+}
+
+/// one scope per top-level directory, so each worker's `repo.statuses()`
+/// call only touches its own subtree instead of every worker re-walking the
+/// whole working tree, plus a single scope bundling top-level loose files
+/// together (too cheap individually to deserve their own worker)
+fn top_level_scopes(
+	repo: &Repository,
+	workdir: &Path,
+) -> Result<Vec<Scope>> {
+	let mut scopes = Vec::new();
+	let mut loose_files = Vec::new();
+	let mut seen = HashSet::new();
+
+	for entry in std::fs::read_dir(workdir)?.flatten() {
+		let name = entry.file_name();
+
+		if name == ".git" {
+			continue;
+		}
+
+		seen.insert(name.to_string_lossy().to_string());
+
+		if entry.file_type().map_or(false, |t| t.is_dir()) {
+			scopes.push(vec![name.to_string_lossy().to_string()]);
+		} else {
+			loose_files.push(name.to_string_lossy().to_string());
+		}
+	}
+
+	// a committed file or directory deleted from disk is missing from
+	// `read_dir`, so every top-level name of HEAD's tree that is not on
+	// disk goes into the loose scope too
+	if let Ok(tree) = repo.head().and_then(|head| head.peel_to_tree()) {
+		for name in tree.iter().filter_map(|e| e.name().map(String::from)) {
+			if seen.insert(name.clone()) {
+				loose_files.push(name);
+			}
+		}
+	}
+
+	if !loose_files.is_empty() {
+		scopes.push(loose_files);
+	}
+
+	Ok(scopes)
+}
+
+fn parallel_status(
+	repo_path: &str,
+	status_type: StatusType,
+	scopes: Vec<Scope>,
+) -> Vec<StatusItem> {
+	let (sender, receiver) = unbounded();
+	let queue = Arc::new(WorkQueue {
+		sender,
+		receiver,
+		pending: AtomicUsize::new(scopes.len()),
+	});
+
+	for scope in scopes {
+		queue.sender.send(scope).ok();
+	}
+
+	let results: Arc<Mutex<Vec<StatusItem>>> =
+		Arc::new(Mutex::new(Vec::new()));
+
+	let handles: Vec<_> = (0..WORKERS)
+		.map(|_| {
+			let queue = Arc::clone(&queue);
+			let results = Arc::clone(&results);
+			let repo_path = repo_path.to_string();
+			thread::spawn(move || {
+				worker(&repo_path, status_type, &queue, &results)
+			})
+		})
+		.collect();
+
+	for handle in handles {
+		handle.join().ok();
+	}
+
+	Arc::try_unwrap(results)
+		.map(|m| m.into_inner().unwrap_or_default())
+		.unwrap_or_default()
+}
+
+fn worker(
+	repo_path: &str,
+	status_type: StatusType,
+	queue: &Arc<WorkQueue>,
+	results: &Arc<Mutex<Vec<StatusItem>>>,
+) {
+	// a `git2::Repository` handle isn't `Sync`, so each worker opens its own
+	let repo = match crate::sync::utils::repo(repo_path) {
+		Ok(repo) => repo,
+		Err(_) => return,
+	};
+
+	loop {
+		match queue.receiver.recv_timeout(Duration::from_millis(50)) {
+			Ok(scope) => {
+				if let Ok(items) =
+					status_scope(&repo, status_type, &scope)
+				{
+					results.lock().unwrap().extend(items);
+				}
+				queue.pending.fetch_sub(1, Ordering::SeqCst);
+			}
+			Err(_) => {
+				if queue.pending.load(Ordering::SeqCst) == 0 {
+					return;
+				}
+			}
+		}
+	}
+}
+
+/// compute status for a single scope with one `repo.statuses()` call
+/// restricted to it via pathspec, so concurrent workers each cover disjoint
+/// parts of the working tree instead of re-walking the same one
+fn status_scope(
+	repo: &Repository,
+	status_type: StatusType,
+	scope: &[String],
+) -> Result<Vec<StatusItem>> {
+	let mut opts = git2::StatusOptions::new();
+	opts.include_untracked(true)
+		.recurse_untracked_dirs(true)
+		.exclude_submodules(false);
+
+	for pattern in scope {
+		opts.pathspec(pattern);
+	}
+
+	let statuses = repo.statuses(Some(&mut opts))?;
+
+	let mut items = Vec::with_capacity(statuses.len());
+
+	for entry in statuses.iter() {
+		let status = entry.status();
+
+		let matches_type = match status_type {
+			StatusType::WorkingDir => {
+				status.is_wt_new()
+					|| status.is_wt_modified()
+					|| status.is_wt_deleted()
+					|| status.is_wt_renamed()
+					|| status.is_wt_typechange()
+					|| status.is_conflicted()
+			}
+			StatusType::Stage => {
+				status.is_index_new()
+					|| status.is_index_modified()
+					|| status.is_index_deleted()
+					|| status.is_index_renamed()
+					|| status.is_index_typechange()
+			}
+		};
+
+		if !matches_type {
+			continue;
+		}
+
+		if let Some(path) = entry.path() {
+			items.push(StatusItem {
+				path: path.to_string(),
+				status: StatusItemType::from(status),
+			});
+		}
+	}
+
+	Ok(items)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::tests::{repo_init, write_commit_file};
+	use std::fs;
+
+	fn find<'a>(
+		items: &'a [StatusItem],
+		path: &str,
+	) -> Option<&'a StatusItem> {
+		items.iter().find(|i| i.path == path)
+	}
+
+	#[test]
+	fn test_status_covers_new_modified_and_deleted() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path = root.as_os_str().to_str().unwrap();
+
+		write_commit_file(&repo, "committed.txt", "a", "commit1");
+		write_commit_file(&repo, "deleted.txt", "d", "commit2");
+
+		// untracked, at the top level
+		fs::write(root.join("untracked.txt"), "u").unwrap();
+
+		// untracked, inside a top-level directory, to exercise the
+		// per-directory worker scope
+		fs::create_dir(root.join("sub")).unwrap();
+		fs::write(root.join("sub/nested.txt"), "n").unwrap();
+
+		// modify an already-committed file
+		fs::write(root.join("committed.txt"), "b").unwrap();
+
+		// delete a committed file entirely; it no longer exists on disk,
+		// so only a pathspec-scoped `repo.statuses()` call (not a
+		// filesystem walk) can still report it
+		fs::remove_file(root.join("deleted.txt")).unwrap();
+
+		let items = status(repo_path, StatusType::WorkingDir).unwrap();
+
+		assert_eq!(
+			find(&items, "untracked.txt").unwrap().status,
+			StatusItemType::New
+		);
+		assert_eq!(
+			find(&items, "sub/nested.txt").unwrap().status,
+			StatusItemType::New
+		);
+		assert_eq!(
+			find(&items, "committed.txt").unwrap().status,
+			StatusItemType::Modified
+		);
+		assert_eq!(
+			find(&items, "deleted.txt").unwrap().status,
+			StatusItemType::Deleted
+		);
+	}
+
+	#[test]
+	fn test_status_ordering_is_stable() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path = root.as_os_str().to_str().unwrap();
+
+		for dir in ["c", "a", "b"] {
+			fs::create_dir(root.join(dir)).unwrap();
+			fs::write(root.join(dir).join("f.txt"), dir).unwrap();
+		}
+
+		let items = status(repo_path, StatusType::WorkingDir).unwrap();
+		let paths: Vec<_> = items.iter().map(|i| i.path.clone()).collect();
+
+		let mut sorted = paths.clone();
+		sorted.sort();
+
+		assert_eq!(paths, sorted);
+	}
+}
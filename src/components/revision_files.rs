@@ -12,30 +12,114 @@ use crate::{
 };
 use anyhow::Result;
 use asyncgit::{
-	sync::{self, CommitId, TreeFile},
+	sync::{
+		self,
+		cache::TtlCache,
+		status::{status, StatusItemType, StatusType},
+		CommitId, TreeFile,
+	},
 	CWD,
 };
 use crossbeam_channel::Sender;
 use crossterm::event::Event;
 use filetreelist::{FileTree, FileTreeItem};
 use std::{
-	collections::BTreeSet,
+	collections::{BTreeMap, BTreeSet},
 	convert::From,
 	path::{Path, PathBuf},
+	time::Duration,
 };
 use tui::{
 	backend::Backend,
 	layout::{Constraint, Direction, Layout, Rect},
+	style::{Color, Style},
 	text::Span,
 	widgets::{Block, Borders},
 	Frame,
 };
 
+/// worst-first ranking used to aggregate a folder's status from its children
+fn status_rank(status: StatusItemType) -> u8 {
+	match status {
+		StatusItemType::Conflicted => 5,
+		StatusItemType::Deleted => 4,
+		StatusItemType::Modified => 3,
+		StatusItemType::Renamed
+		| StatusItemType::Typechange => 2,
+		StatusItemType::New => 1,
+	}
+}
+
+fn status_marker(status: StatusItemType) -> &'static str {
+	match status {
+		StatusItemType::Conflicted => "U",
+		StatusItemType::Deleted => "D",
+		StatusItemType::Modified => "M",
+		StatusItemType::Renamed => "R",
+		StatusItemType::Typechange => "T",
+		StatusItemType::New => "A",
+	}
+}
+
+fn status_color(status: StatusItemType) -> Color {
+	match status {
+		StatusItemType::Conflicted => Color::Red,
+		StatusItemType::Deleted => Color::Red,
+		StatusItemType::Modified => Color::Yellow,
+		StatusItemType::Renamed
+		| StatusItemType::Typechange => Color::Blue,
+		StatusItemType::New => Color::Green,
+	}
+}
+
+/// flatten a working-tree status scan into a path -> status map, with every
+/// ancestor directory carrying the worst status among its descendants so a
+/// collapsed folder still signals it contains changes
+fn build_status_map(
+) -> BTreeMap<PathBuf, StatusItemType> {
+	let mut map = BTreeMap::new();
+
+	let items = status(CWD, StatusType::WorkingDir)
+		.unwrap_or_default();
+
+	for item in items {
+		let path = PathBuf::from(&item.path);
+
+		insert_worst(&mut map, path.clone(), item.status);
+
+		for ancestor in path.ancestors().skip(1) {
+			if ancestor.as_os_str().is_empty() {
+				break;
+			}
+			insert_worst(&mut map, ancestor.to_path_buf(), item.status);
+		}
+	}
+
+	map
+}
+
+fn insert_worst(
+	map: &mut BTreeMap<PathBuf, StatusItemType>,
+	path: PathBuf,
+	status: StatusItemType,
+) {
+	map.entry(path)
+		.and_modify(|existing| {
+			if status_rank(status) > status_rank(*existing) {
+				*existing = status;
+			}
+		})
+		.or_insert(status);
+}
+
 enum Focus {
 	Tree,
 	File,
 }
 
+const TREE_CACHE_CAPACITY: usize = 20;
+const CACHE_TTL: Duration = Duration::from_secs(60);
+
 pub struct RevisionFilesComponent {
 	queue: Queue,
 	theme: SharedTheme,
@@ -47,6 +131,8 @@ pub struct RevisionFilesComponent {
 	revision: Option<CommitId>,
 	focus: Focus,
 	key_config: SharedKeyConfig,
+	file_status: BTreeMap<PathBuf, StatusItemType>,
+	tree_cache: TtlCache<CommitId, Vec<TreeFile>>,
 }
 
 impl RevisionFilesComponent {
@@ -71,6 +157,8 @@ impl RevisionFilesComponent {
 			revision: None,
 			focus: Focus::Tree,
 			key_config,
+			file_status: BTreeMap::new(),
+			tree_cache: TtlCache::new(TREE_CACHE_CAPACITY, CACHE_TTL),
 		}
 	}
 
@@ -79,17 +167,33 @@ impl RevisionFilesComponent {
 		let same_id =
 			self.revision.map(|c| c == commit).unwrap_or_default();
 		if !same_id {
-			self.files = sync::tree_files(CWD, commit)?;
+			self.files = self.tree_files_cached(commit)?;
 			let filenames: Vec<&Path> =
 				self.files.iter().map(|f| f.path.as_path()).collect();
 			self.tree = FileTree::new(&filenames, &BTreeSet::new())?;
 			self.tree.collapse_but_root();
 			self.revision = Some(commit);
+			self.file_status = build_status_map();
 		}
 
 		Ok(())
 	}
 
+	/// tree listing for `commit`, served from the TTL cache when available
+	fn tree_files_cached(
+		&self,
+		commit: CommitId,
+	) -> Result<Vec<TreeFile>> {
+		if let Some(files) = self.tree_cache.get(&commit) {
+			return Ok(files);
+		}
+
+		let files = sync::tree_files(CWD, commit)?;
+		self.tree_cache.insert(commit, files.clone());
+
+		Ok(files)
+	}
+
 	///
 	pub fn update(&mut self, ev: AsyncNotification) {
 		self.current_file.update(ev);
@@ -104,6 +208,7 @@ impl RevisionFilesComponent {
 		item: &'a FileTreeItem,
 		theme: &SharedTheme,
 		selected: bool,
+		file_status: &BTreeMap<PathBuf, StatusItemType>,
 	) -> Span<'a> {
 		let path = item.info().path_str();
 		let indent = item.info().indent();
@@ -125,8 +230,25 @@ impl RevisionFilesComponent {
 			symbol::EMPTY_STR
 		};
 
-		let path = format!("{}{}{}", indent_str, path_arrow, path);
-		Span::styled(path, theme.file_tree_item(is_path, selected))
+		// the map is keyed by repo-relative path, while `path` is only the
+		// item's display name
+		let full_path = item.info().full_path_str();
+		let status = file_status.get(Path::new(
+			full_path.strip_prefix("./").unwrap_or(full_path),
+		));
+		let marker = status
+			.map(|s| format!("{} ", status_marker(*s)))
+			.unwrap_or_default();
+
+		let path =
+			format!("{}{}{}{}", indent_str, marker, path_arrow, path);
+
+		let style = status.map_or_else(
+			|| theme.file_tree_item(is_path, selected),
+			|s| Style::default().fg(status_color(*s)),
+		);
+
+		Span::styled(path, style)
 	}
 
 	fn blame(&self) -> bool {
@@ -198,7 +320,12 @@ impl RevisionFilesComponent {
 			.tree
 			.iterate(self.scroll.get_top(), tree_height)
 			.map(|(item, selected)| {
-				Self::tree_item_to_span(item, &self.theme, selected)
+				Self::tree_item_to_span(
+					item,
+					&self.theme,
+					selected,
+					&self.file_status,
+				)
 			});
 
 		let is_tree_focused = matches!(self.focus, Focus::Tree);
@@ -357,3 +484,109 @@ fn tree_nav(
 		false
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_insert_worst_keeps_the_worse_status() {
+		let mut map = BTreeMap::new();
+		let path = PathBuf::from("a.txt");
+
+		insert_worst(&mut map, path.clone(), StatusItemType::New);
+		assert_eq!(map[&path], StatusItemType::New);
+
+		// modified outranks new, so it replaces the existing entry
+		insert_worst(&mut map, path.clone(), StatusItemType::Modified);
+		assert_eq!(map[&path], StatusItemType::Modified);
+
+		// new ranks below modified, so it doesn't overwrite it
+		insert_worst(&mut map, path.clone(), StatusItemType::New);
+		assert_eq!(map[&path], StatusItemType::Modified);
+
+		// conflicted outranks everything
+		insert_worst(&mut map, path.clone(), StatusItemType::Conflicted);
+		assert_eq!(map[&path], StatusItemType::Conflicted);
+	}
+
+	#[test]
+	fn test_status_rank_orders_conflicted_worst_and_new_best() {
+		assert!(
+			status_rank(StatusItemType::Conflicted)
+				> status_rank(StatusItemType::Deleted)
+		);
+		assert!(
+			status_rank(StatusItemType::Deleted)
+				> status_rank(StatusItemType::Modified)
+		);
+		assert!(
+			status_rank(StatusItemType::Modified)
+				> status_rank(StatusItemType::Renamed)
+		);
+		assert!(
+			status_rank(StatusItemType::Renamed)
+				> status_rank(StatusItemType::New)
+		);
+	}
+
+	#[test]
+	fn test_insert_worst_rolls_up_ancestor_directories() {
+		// mirrors what `build_status_map` does for every scanned path:
+		// the file's own status, plus every ancestor directory carrying
+		// the worst status among its descendants
+		let mut map = BTreeMap::new();
+		let path = PathBuf::from("src/components/foo.rs");
+
+		insert_worst(&mut map, path.clone(), StatusItemType::Modified);
+		for ancestor in path.ancestors().skip(1) {
+			if ancestor.as_os_str().is_empty() {
+				break;
+			}
+			insert_worst(
+				&mut map,
+				ancestor.to_path_buf(),
+				StatusItemType::Modified,
+			);
+		}
+
+		assert_eq!(
+			map[&PathBuf::from("src/components/foo.rs")],
+			StatusItemType::Modified
+		);
+		assert_eq!(
+			map[&PathBuf::from("src/components")],
+			StatusItemType::Modified
+		);
+		assert_eq!(map[&PathBuf::from("src")], StatusItemType::Modified);
+
+		// a second, worse-status file under the same folders should bump
+		// the ancestors' rolled-up status without touching the first
+		// file's own entry
+		let other = PathBuf::from("src/components/bar.rs");
+		insert_worst(&mut map, other.clone(), StatusItemType::Conflicted);
+		for ancestor in other.ancestors().skip(1) {
+			if ancestor.as_os_str().is_empty() {
+				break;
+			}
+			insert_worst(
+				&mut map,
+				ancestor.to_path_buf(),
+				StatusItemType::Conflicted,
+			);
+		}
+
+		assert_eq!(
+			map[&PathBuf::from("src/components")],
+			StatusItemType::Conflicted
+		);
+		assert_eq!(
+			map[&PathBuf::from("src")],
+			StatusItemType::Conflicted
+		);
+		assert_eq!(
+			map[&PathBuf::from("src/components/foo.rs")],
+			StatusItemType::Modified
+		);
+	}
+}